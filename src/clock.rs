@@ -0,0 +1,170 @@
+use core::ops::{Add, Sub};
+
+use crate::error::Error;
+use crate::source::{ClockSource, ManualClock};
+use crate::uncertainty::{self, ErrorInterval};
+use crate::{Result, Timestamp};
+
+/// A hybrid logical clock, wrapping a [`ClockSource`] of physical time and
+/// folding it together with observed remote [`Timestamp`]s to produce
+/// timestamps that are both causally ordered and close to physical time.
+///
+/// Implements the algorithm from Kulkarni et al., "Logical Physical Clocks
+/// and Consistent Snapshots in Globally Distributed Databases".
+pub struct Clock<S: ClockSource> {
+    source: S,
+    last: Timestamp<S::Time>,
+    last_pt: Option<S::Time>,
+    max_diff: Option<S::Delta>,
+}
+
+impl<S: ClockSource> Clock<S>
+where
+    S::Time: Sub<Output = S::Delta>,
+    S::Delta: Copy + PartialOrd,
+{
+    /// Creates a new clock reading its initial physical time from `source`.
+    pub fn new(mut source: S) -> Result<Self> {
+        let pt = source.now()?;
+        Ok(Clock {
+            source,
+            last: Timestamp { time: pt, count: 0 },
+            last_pt: None,
+            max_diff: None,
+        })
+    }
+
+    /// Rejects [`observe`](Self::observe)d timestamps whose physical
+    /// component is more than `max_diff` ahead of our own physical clock.
+    pub fn with_max_diff(mut self, max_diff: S::Delta) -> Self {
+        self.max_diff = Some(max_diff);
+        self
+    }
+
+    /// Gives mutable access to the underlying clock source, e.g. to drive a
+    /// [`ManualClock`] in tests.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    /// Returns a new local timestamp, advancing the clock if physical time
+    /// has moved since the last reading.
+    pub fn now(&mut self) -> Result<Timestamp<S::Time>> {
+        let pt = self.source.now()?;
+        if self.last_pt != Some(pt) {
+            self.tick(pt);
+        }
+        Ok(self.last)
+    }
+
+    /// Merges an observed remote timestamp into this clock, rejecting it
+    /// with [`Error::OffsetTooGreat`] if it is too far ahead of our own
+    /// physical clock (see [`with_max_diff`](Self::with_max_diff)).
+    pub fn observe(&mut self, msg: &Timestamp<S::Time>) -> Result<()> {
+        let pt = self.source.now()?;
+        if let Some(max_diff) = self.max_diff {
+            if msg.time > pt && msg.time - pt > max_diff {
+                return Err(Error::OffsetTooGreat);
+            }
+        }
+
+        let new_time = self.last.time.max(msg.time).max(pt);
+        let count = if new_time == self.last.time && new_time == msg.time {
+            self.last.count.max(msg.count) + 1
+        } else if new_time == self.last.time {
+            self.last.count + 1
+        } else if new_time == msg.time {
+            msg.count + 1
+        } else {
+            0
+        };
+        self.last = Timestamp {
+            time: new_time,
+            count,
+        };
+        self.last_pt = Some(pt);
+        Ok(())
+    }
+
+    fn tick(&mut self, pt: S::Time) {
+        let new_time = if pt > self.last.time { pt } else { self.last.time };
+        let count = if new_time == self.last.time {
+            self.last.count + 1
+        } else {
+            0
+        };
+        self.last = Timestamp {
+            time: new_time,
+            count,
+        };
+        self.last_pt = Some(pt);
+    }
+}
+
+impl<S: ClockSource> Clock<S>
+where
+    S::Time: Add<S::Delta, Output = S::Time> + Sub<S::Delta, Output = S::Time> + Sub<Output = S::Delta>,
+    S::Delta: Copy + Ord,
+{
+    /// Returns the current physical time as an [`ErrorInterval`] of
+    /// `±own_error` around [`now`](Self::now)'s point estimate, for callers
+    /// that track their own clock's drift/uncertainty.
+    pub fn now_interval(&mut self, own_error: S::Delta) -> Result<ErrorInterval<S::Time, S::Delta>> {
+        let pt = self.now()?;
+        Ok(ErrorInterval::new(pt.time, own_error))
+    }
+
+    /// Like [`observe`](Self::observe), but checks `remote`'s uncertainty
+    /// interval against our own (widened to `own_error`) instead of a
+    /// single point: accepted outright if the intervals overlap, and
+    /// otherwise rejected only once `remote`'s earliest bound is more than
+    /// [`with_max_diff`](Self::with_max_diff)'s bound past our latest one.
+    ///
+    /// On acceptance, `self.last` advances to `remote`'s latest bound if that
+    /// is later, with its count restarting at `0` (an interval carries no
+    /// count to continue from); otherwise the count is incremented. A later
+    /// `now()` is therefore never ordered before `remote`'s latest bound.
+    pub fn observe_interval(
+        &mut self,
+        own_error: S::Delta,
+        remote: &ErrorInterval<S::Time, S::Delta>,
+    ) -> Result<()> {
+        let local = self.now_interval(own_error)?;
+        if let Some(max_diff) = self.max_diff {
+            uncertainty::accept_remote(&local, remote, max_diff)?;
+        }
+
+        let new_time = self.last.time.max(remote.latest());
+        let count = if new_time == self.last.time {
+            self.last.count + 1
+        } else {
+            0
+        };
+        self.last = Timestamp {
+            time: new_time,
+            count,
+        };
+        Ok(())
+    }
+}
+
+impl Clock<ManualClock> {
+    /// Creates a `Clock` driven by a [`ManualClock`] seeded at `time`, for
+    /// tests.
+    pub fn manual(time: u64) -> Result<Self> {
+        Clock::new(ManualClock::new(time))
+    }
+}
+
+impl<S: ClockSource> core::ops::Deref for Clock<S> {
+    type Target = S;
+    fn deref(&self) -> &S {
+        &self.source
+    }
+}
+
+impl<S: ClockSource> core::ops::DerefMut for Clock<S> {
+    fn deref_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+}
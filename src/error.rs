@@ -0,0 +1,40 @@
+use core::fmt;
+
+/// The result type used throughout this crate.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Errors that can occur while reading or comparing clocks.
+#[derive(Debug)]
+pub enum Error {
+    /// An observed timestamp was too far ahead of our own clock to be
+    /// trusted; see `Clock::with_max_diff`.
+    OffsetTooGreat,
+    /// The underlying system clock could not be read, e.g. because it is
+    /// set to a time before `SystemTime::UNIX_EPOCH`.
+    #[cfg(feature = "std")]
+    SystemTimeError(std::time::SystemTimeError),
+    /// A timestamp encoding was malformed, out of range, or used a field
+    /// width/epoch this build doesn't recognize.
+    InvalidEncoding,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::OffsetTooGreat => write!(fmt, "observed clock offset is too great"),
+            #[cfg(feature = "std")]
+            Error::SystemTimeError(e) => write!(fmt, "system time error: {}", e),
+            Error::InvalidEncoding => write!(fmt, "invalid timestamp encoding"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(feature = "std")]
+impl From<std::time::SystemTimeError> for Error {
+    fn from(e: std::time::SystemTimeError) -> Self {
+        Error::SystemTimeError(e)
+    }
+}
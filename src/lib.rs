@@ -0,0 +1,40 @@
+//! Hybrid logical clocks: timestamps that are causally ordered like Lamport
+//! clocks, but stay close to physical wall-clock time.
+//!
+//! The core types ([`Timestamp`], [`Clock`], [`source::ClockSource`]) are
+//! `no_std`-compatible; wall-clock sources that need `std::time::SystemTime`
+//! (such as [`source::WallMS`]) are gated behind the default-on `std`
+//! feature.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "serialization")]
+#[macro_use]
+extern crate serde;
+
+mod clock;
+mod error;
+pub mod source;
+pub mod uncertainty;
+
+#[cfg(feature = "serialization")]
+mod serde_impl;
+
+pub use crate::clock::Clock;
+pub use crate::error::{Error, Result};
+pub use crate::source::{ClockSource, ManualClock};
+pub use crate::uncertainty::{ErrorInterval, UncertainClockSource};
+
+/// A hybrid logical clock timestamp: a physical-time component `time`
+/// plus a logical `count` that disambiguates events sharing the same
+/// physical time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp<T> {
+    pub time: T,
+    pub count: u16,
+}
+
+impl<T: core::fmt::Display> core::fmt::Display for Timestamp<T> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "{}+{}", self.time, self.count)
+    }
+}
@@ -10,7 +10,7 @@ impl<T: ser::Serialize + Copy> ser::Serialize for crate::Timestamp<T> {
 }
 
 impl<'de, T: de::Deserialize<'de>> de::Deserialize<'de> for crate::Timestamp<T> {
-    fn deserialize<D>(deserializer: D) -> ::std::result::Result<crate::Timestamp<T>, D::Error>
+    fn deserialize<D>(deserializer: D) -> Result<crate::Timestamp<T>, D::Error>
     where
         D: de::Deserializer<'de>,
     {
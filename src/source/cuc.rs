@@ -0,0 +1,312 @@
+//! A configurable-resolution, self-describing wall-clock encoding, in the
+//! spirit of the CCSDS Unsegmented Time Code (CUC): a leading preamble byte
+//! records the field widths and epoch actually used, so a decoder doesn't
+//! need to be told the resolution out of band. Unlike [`WallMST`](super::WallMST)'s
+//! fixed 8-byte layout, callers pick how many coarse (whole-second) and fine
+//! (sub-second) octets they need, trading precision for wire size.
+//!
+//! As with the TAI64N and `to_bytes` encodings, the byte representation
+//! sorts lexicographically the same as chronological order. [`encode`](CucFormat::encode)
+//! always emits exactly `max_fine_octets` fine-time octets (scaled to that
+//! fixed resolution) rather than shrinking to however few a given value
+//! happens to need: the width is therefore constant for every encode of a
+//! given [`CucFormat`], so the preamble byte describing it never disagrees
+//! with two encodes' actual time fields, and the big-endian coarse/fine
+//! octets alone determine ordering.
+
+use core::convert::TryInto;
+use core::time::Duration;
+
+use crate::error::Error;
+use crate::Result;
+
+/// The largest number of coarse-time octets this format supports.
+pub const MAX_COARSE_OCTETS: u8 = 4;
+/// The largest number of fine-time octets this format supports.
+pub const MAX_FINE_OCTETS: u8 = 3;
+/// `1 (preamble) + MAX_COARSE_OCTETS + MAX_FINE_OCTETS`.
+pub const MAX_ENCODED_LEN: usize = 1 + MAX_COARSE_OCTETS as usize + MAX_FINE_OCTETS as usize;
+
+/// The epoch a [`CucFormat`] measures coarse time from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CucEpoch {
+    /// 1970-01-01T00:00:00Z, the Unix epoch.
+    Unix,
+    /// [`WallMST::EPOCH_2020`](super::WallMST::EPOCH_2020), 2020-02-20T00:00:00Z.
+    Epoch2020,
+}
+
+impl CucEpoch {
+    fn id(self) -> u8 {
+        match self {
+            CucEpoch::Unix => 0,
+            CucEpoch::Epoch2020 => 1,
+        }
+    }
+
+    fn from_id(id: u8) -> Result<Self> {
+        match id {
+            0 => Ok(CucEpoch::Unix),
+            1 => Ok(CucEpoch::Epoch2020),
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+
+    fn offset_secs(self) -> u64 {
+        match self {
+            CucEpoch::Unix => 0,
+            CucEpoch::Epoch2020 => super::WallMST::EPOCH_2020,
+        }
+    }
+}
+
+/// Describes the field widths a [`CucFormat`] uses when encoding: every
+/// value is scaled to exactly `max_fine_octets` fine-time octets, never
+/// fewer, so the wire width is fixed for a given format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CucFormat {
+    /// Number of big-endian whole-second octets, `1..=MAX_COARSE_OCTETS`.
+    pub coarse_octets: u8,
+    /// Number of big-endian sub-second octets, `0..=MAX_FINE_OCTETS`.
+    pub max_fine_octets: u8,
+    /// The epoch coarse time is measured from.
+    pub epoch: CucEpoch,
+}
+
+/// A preamble byte describing `(coarse_octets, fine_octets, epoch)`, encoded
+/// as `0bCCFFEEEE`: 2 bits of `coarse_octets - 1`, 2 bits of `fine_octets`,
+/// and a 4-bit epoch identifier.
+///
+/// All three fields are fixed for a given [`CucFormat`] — `fine_octets` is
+/// always `max_fine_octets`, never shrunk per value — so the preamble byte
+/// is identical for every encode made with the same format and never
+/// perturbs the comparison of two encodes' coarse/fine octets.
+fn encode_preamble(coarse_octets: u8, fine_octets: u8, epoch: CucEpoch) -> u8 {
+    ((coarse_octets - 1) << 6) | (fine_octets << 4) | epoch.id()
+}
+
+fn decode_preamble(preamble: u8) -> Result<(u8, u8, CucEpoch)> {
+    let coarse_octets = ((preamble >> 6) & 0b11) + 1;
+    let fine_octets = (preamble >> 4) & 0b11;
+    let epoch = CucEpoch::from_id(preamble & 0b1111)?;
+    Ok((coarse_octets, fine_octets, epoch))
+}
+
+/// A CUC-encoded timestamp: a fixed-capacity buffer plus the number of
+/// leading bytes that are actually in use, for `no_std`-friendly encoding
+/// without allocation.
+#[derive(Debug, Clone, Copy)]
+pub struct CucEncoded {
+    bytes: [u8; MAX_ENCODED_LEN],
+    len: u8,
+}
+
+impl CucEncoded {
+    /// Returns the encoded bytes: a preamble byte, then the coarse and fine
+    /// fields.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+impl CucFormat {
+    /// Encodes `since_epoch` using this format's `coarse_octets`, scaling
+    /// the sub-second remainder to exactly `max_fine_octets` fine-time
+    /// octets (never fewer), so every encode made with this format has the
+    /// same wire width and the same preamble byte.
+    pub fn encode(&self, since_epoch: Duration) -> Result<CucEncoded> {
+        if self.coarse_octets == 0 || self.coarse_octets > MAX_COARSE_OCTETS {
+            return Err(Error::InvalidEncoding);
+        }
+        if self.max_fine_octets > MAX_FINE_OCTETS {
+            return Err(Error::InvalidEncoding);
+        }
+
+        let coarse_secs = since_epoch
+            .as_secs()
+            .checked_sub(self.epoch.offset_secs())
+            .ok_or(Error::InvalidEncoding)?;
+        let coarse_bytes = coarse_secs.to_be_bytes();
+        let coarse_start = 8 - self.coarse_octets as usize;
+        if coarse_bytes[..coarse_start].iter().any(|&b| b != 0) {
+            // `coarse_secs` doesn't fit in `coarse_octets`.
+            return Err(Error::InvalidEncoding);
+        }
+
+        let fine_octets = self.max_fine_octets;
+        let fine_value = scale_nanos_to_fine(since_epoch.subsec_nanos(), fine_octets);
+        let fine_bytes = fine_value.to_be_bytes();
+
+        let mut bytes = [0u8; MAX_ENCODED_LEN];
+        bytes[0] = encode_preamble(self.coarse_octets, fine_octets, self.epoch);
+        let coarse_end = 1 + self.coarse_octets as usize;
+        bytes[1..coarse_end].copy_from_slice(&coarse_bytes[coarse_start..]);
+        let fine_start = 8 - fine_octets as usize;
+        bytes[coarse_end..coarse_end + fine_octets as usize]
+            .copy_from_slice(&fine_bytes[fine_start..]);
+
+        Ok(CucEncoded {
+            bytes,
+            len: (coarse_end + fine_octets as usize) as u8,
+        })
+    }
+}
+
+/// Decodes a self-describing CUC timestamp, returning the `Duration` since
+/// the unix epoch and the number of bytes consumed from `bytes`.
+pub fn decode(bytes: &[u8]) -> Result<(Duration, usize)> {
+    let (&preamble, rest) = bytes.split_first().ok_or(Error::InvalidEncoding)?;
+    let (coarse_octets, fine_octets, epoch) = decode_preamble(preamble)?;
+    let needed = coarse_octets as usize + fine_octets as usize;
+    if rest.len() < needed {
+        return Err(Error::InvalidEncoding);
+    }
+
+    let mut coarse_buf = [0u8; 8];
+    coarse_buf[8 - coarse_octets as usize..].copy_from_slice(&rest[..coarse_octets as usize]);
+    let coarse_secs = u64::from_be_bytes(coarse_buf) + epoch.offset_secs();
+
+    let mut fine_buf = [0u8; 8];
+    let fine_bytes = &rest[coarse_octets as usize..needed];
+    fine_buf[8 - fine_octets as usize..].copy_from_slice(fine_bytes);
+    let fine_value = u64::from_be_bytes(fine_buf);
+    let subsec_nanos = scale_fine_to_nanos(fine_value, fine_octets);
+
+    Ok((Duration::new(coarse_secs, subsec_nanos), 1 + needed))
+}
+
+/// Scales a nanosecond count down into `fine_octets` big-endian octets of
+/// fixed-point seconds (i.e. units of `2^-(8 * fine_octets)` seconds).
+fn scale_nanos_to_fine(subsec_nanos: u32, fine_octets: u8) -> u64 {
+    if fine_octets == 0 {
+        return 0;
+    }
+    let scale = 1u64 << (8 * fine_octets as u32);
+    ((subsec_nanos as u64) * scale) / super::NANOS_PER_SEC
+}
+
+/// Inverse of [`scale_nanos_to_fine`].
+fn scale_fine_to_nanos(fine_value: u64, fine_octets: u8) -> u32 {
+    if fine_octets == 0 {
+        return 0;
+    }
+    let scale = 1u64 << (8 * fine_octets as u32);
+    ((fine_value * super::NANOS_PER_SEC) / scale)
+        .try_into()
+        .unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use suppositions::generators::*;
+    use suppositions::*;
+
+    // `coarse_octets` is fixed at the maximum so any `u32`-ranged second
+    // count (see `durations` below) fits without truncation.
+    fn formats() -> Box<dyn GeneratorObject<Item = CucFormat>> {
+        u8s()
+            .map(|max_fine_octets| CucFormat {
+                coarse_octets: MAX_COARSE_OCTETS,
+                max_fine_octets: max_fine_octets % (MAX_FINE_OCTETS + 1),
+                epoch: CucEpoch::Unix,
+            })
+            .boxed()
+    }
+
+    // Durations that fit in `MAX_COARSE_OCTETS` whole-second octets.
+    fn durations() -> Box<dyn GeneratorObject<Item = Duration>> {
+        (u32s(), u32s())
+            .map(|(secs, nanos)| Duration::new(secs as u64, nanos % 1_000_000_000))
+            .boxed()
+    }
+
+    #[test]
+    fn should_round_trip_to_the_chosen_resolution() {
+        property((formats(), durations())).check(|(format, since_epoch)| {
+            let encoded = format.encode(since_epoch).expect("encode");
+            let (decoded, consumed) = decode(encoded.as_bytes()).expect("decode");
+            let expected_nanos = scale_fine_to_nanos(
+                scale_nanos_to_fine(since_epoch.subsec_nanos(), format.max_fine_octets),
+                format.max_fine_octets,
+            );
+            consumed == encoded.as_bytes().len()
+                && decoded.as_secs() == since_epoch.as_secs()
+                && decoded.subsec_nanos() == expected_nanos
+        });
+    }
+
+    #[test]
+    fn byte_repr_should_order_as_timestamps() {
+        property((durations(), durations())).check(|(a, b)| {
+            let format = CucFormat {
+                coarse_octets: 4,
+                max_fine_octets: MAX_FINE_OCTETS,
+                epoch: CucEpoch::Unix,
+            };
+            let ea = format.encode(a).expect("encode a");
+            let eb = format.encode(b).expect("encode b");
+            a.cmp(&b) == ea.as_bytes().cmp(eb.as_bytes())
+        });
+    }
+
+    #[test]
+    fn byte_repr_should_order_mixed_precision_values() {
+        // `durations()` almost never generates two values whose sub-second
+        // remainders would, under a naive smallest-width encoding, round
+        // trip exactly at different fine-octet counts. Exercise that case
+        // directly: a later whole-second value (no fine-time content) next
+        // to an earlier sub-second one.
+        let format = CucFormat {
+            coarse_octets: 4,
+            max_fine_octets: MAX_FINE_OCTETS,
+            epoch: CucEpoch::Unix,
+        };
+        let later_whole_second = Duration::new(100, 0);
+        let earlier_sub_second = Duration::new(5, 500_000_000);
+
+        let ea = format.encode(later_whole_second).expect("encode a");
+        let eb = format.encode(earlier_sub_second).expect("encode b");
+        assert_eq!(
+            later_whole_second.cmp(&earlier_sub_second),
+            ea.as_bytes().cmp(eb.as_bytes())
+        );
+    }
+
+    #[test]
+    fn byte_repr_should_order_same_second_different_precision_needs() {
+        // Both values are sub-second and share a coarse second, but under
+        // the old smallest-fine-octets-that-round-trips scheme they'd pick
+        // different widths: 996_093_750ns is exact at 1 fine octet
+        // (255/256s), while 1_953_125ns needs 2 (1/512s). That used to rank
+        // the earlier instant after the later one; fixed-width encoding
+        // must not.
+        let format = CucFormat {
+            coarse_octets: 4,
+            max_fine_octets: MAX_FINE_OCTETS,
+            epoch: CucEpoch::Unix,
+        };
+        let later = Duration::new(100, 996_093_750);
+        let earlier = Duration::new(100, 1_953_125);
+
+        let ea = format.encode(later).expect("encode a");
+        let eb = format.encode(earlier).expect("encode b");
+        assert_eq!(later.cmp(&earlier), ea.as_bytes().cmp(eb.as_bytes()));
+    }
+
+    #[test]
+    fn encode_always_uses_max_fine_octets() {
+        // Whole seconds no longer shrink the wire width to zero fine
+        // octets: every encode made with this format is the same length.
+        let format = CucFormat {
+            coarse_octets: 4,
+            max_fine_octets: MAX_FINE_OCTETS,
+            epoch: CucEpoch::Unix,
+        };
+        let encoded = format.encode(Duration::new(12345, 0)).expect("encode");
+        assert_eq!(
+            encoded.as_bytes().len(),
+            1 + 4 + MAX_FINE_OCTETS as usize
+        );
+    }
+}
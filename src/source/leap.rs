@@ -0,0 +1,151 @@
+//! An optional leap-second-aware conversion between UTC and the TAI-like
+//! physical seconds [`WallMST`](super::WallMST) stores internally.
+//!
+//! `WallMST::from_timespec`/`duration_since_epoch` treat `SystemTime` as
+//! plain UTC seconds, so a positive UTC leap second (`23:59:60`) can make
+//! the physical component repeat or appear to run backward. The
+//! [`LeapSecondTable`] in this module lets callers supply a sorted table of
+//! `(utc_threshold, tai_minus_utc)` entries so that conversion instead
+//! yields a strictly monotonic TAI-referenced seconds count; when no table
+//! is supplied, callers keep today's plain UTC-based math.
+
+/// One entry in a [`LeapSecondTable`]: from `utc_threshold_secs` (seconds
+/// since the unix epoch, inclusive) onward, TAI is `tai_minus_utc` seconds
+/// ahead of UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeapSecondEntry {
+    pub utc_threshold_secs: u64,
+    pub tai_minus_utc: i64,
+}
+
+/// A sorted table of historical (or custom) leap seconds, used to convert
+/// between UTC seconds and a strictly monotonic TAI-like seconds count.
+///
+/// Entries must be sorted ascending by `utc_threshold_secs`; lookups for
+/// times before the first entry use an offset of `0`.
+#[derive(Debug, Clone, Copy)]
+pub struct LeapSecondTable<'a> {
+    entries: &'a [LeapSecondEntry],
+}
+
+impl<'a> LeapSecondTable<'a> {
+    /// Wraps a sorted table of leap-second entries.
+    pub const fn new(entries: &'a [LeapSecondEntry]) -> Self {
+        LeapSecondTable { entries }
+    }
+
+    /// The offset to add when converting a UTC seconds-since-epoch count
+    /// into the physical (TAI-like) seconds count: the `tai_minus_utc` of
+    /// the largest threshold `<= utc_secs`, or `0` if none applies.
+    pub fn utc_to_physical_offset(&self, utc_secs: u64) -> i64 {
+        let mut offset = 0;
+        for entry in self.entries {
+            if entry.utc_threshold_secs <= utc_secs {
+                offset = entry.tai_minus_utc;
+            } else {
+                break;
+            }
+        }
+        offset
+    }
+
+    /// The inverse of [`utc_to_physical_offset`](Self::utc_to_physical_offset):
+    /// the offset to subtract when converting a physical (TAI-like) seconds
+    /// count back into UTC.
+    pub fn physical_to_utc_offset(&self, physical_secs: u64) -> i64 {
+        let mut offset = 0;
+        for entry in self.entries {
+            let tai_threshold = (entry.utc_threshold_secs as i64 + entry.tai_minus_utc) as u64;
+            if tai_threshold <= physical_secs {
+                offset = entry.tai_minus_utc;
+            } else {
+                break;
+            }
+        }
+        offset
+    }
+}
+
+/// A built-in table covering the leap seconds inserted since the unix
+/// epoch, ending with the 2017-01-01 leap second (TAI - UTC = 37s).
+/// Applications that need to stay current as new leap seconds are
+/// announced should supply their own table instead.
+pub static DEFAULT_LEAP_SECONDS: &[LeapSecondEntry] = &[
+    LeapSecondEntry { utc_threshold_secs: 78_796_800, tai_minus_utc: 11 }, // 1972-07-01
+    LeapSecondEntry { utc_threshold_secs: 94_694_400, tai_minus_utc: 12 }, // 1973-01-01
+    LeapSecondEntry { utc_threshold_secs: 126_230_400, tai_minus_utc: 13 }, // 1974-01-01
+    LeapSecondEntry { utc_threshold_secs: 157_766_400, tai_minus_utc: 14 }, // 1975-01-01
+    LeapSecondEntry { utc_threshold_secs: 189_302_400, tai_minus_utc: 15 }, // 1976-01-01
+    LeapSecondEntry { utc_threshold_secs: 220_924_800, tai_minus_utc: 16 }, // 1977-01-01
+    LeapSecondEntry { utc_threshold_secs: 252_460_800, tai_minus_utc: 17 }, // 1978-01-01
+    LeapSecondEntry { utc_threshold_secs: 283_996_800, tai_minus_utc: 18 }, // 1979-01-01
+    LeapSecondEntry { utc_threshold_secs: 315_532_800, tai_minus_utc: 19 }, // 1980-01-01
+    LeapSecondEntry { utc_threshold_secs: 362_793_600, tai_minus_utc: 20 }, // 1981-07-01
+    LeapSecondEntry { utc_threshold_secs: 394_329_600, tai_minus_utc: 21 }, // 1982-07-01
+    LeapSecondEntry { utc_threshold_secs: 425_865_600, tai_minus_utc: 22 }, // 1983-07-01
+    LeapSecondEntry { utc_threshold_secs: 489_024_000, tai_minus_utc: 23 }, // 1985-07-01
+    LeapSecondEntry { utc_threshold_secs: 567_993_600, tai_minus_utc: 24 }, // 1988-01-01
+    LeapSecondEntry { utc_threshold_secs: 631_152_000, tai_minus_utc: 25 }, // 1990-01-01
+    LeapSecondEntry { utc_threshold_secs: 662_688_000, tai_minus_utc: 26 }, // 1991-01-01
+    LeapSecondEntry { utc_threshold_secs: 709_948_800, tai_minus_utc: 27 }, // 1992-07-01
+    LeapSecondEntry { utc_threshold_secs: 741_484_800, tai_minus_utc: 28 }, // 1993-07-01
+    LeapSecondEntry { utc_threshold_secs: 773_020_800, tai_minus_utc: 29 }, // 1994-07-01
+    LeapSecondEntry { utc_threshold_secs: 820_454_400, tai_minus_utc: 30 }, // 1996-01-01
+    LeapSecondEntry { utc_threshold_secs: 867_715_200, tai_minus_utc: 31 }, // 1997-07-01
+    LeapSecondEntry { utc_threshold_secs: 915_148_800, tai_minus_utc: 32 }, // 1999-01-01
+    LeapSecondEntry { utc_threshold_secs: 1_136_073_600, tai_minus_utc: 33 }, // 2006-01-01
+    LeapSecondEntry { utc_threshold_secs: 1_230_768_000, tai_minus_utc: 34 }, // 2009-01-01
+    LeapSecondEntry { utc_threshold_secs: 1_341_100_800, tai_minus_utc: 35 }, // 2012-07-01
+    LeapSecondEntry { utc_threshold_secs: 1_435_708_800, tai_minus_utc: 36 }, // 2015-07-01
+    LeapSecondEntry { utc_threshold_secs: 1_483_228_800, tai_minus_utc: 37 }, // 2017-01-01
+];
+
+/// The built-in leap-second table; see [`DEFAULT_LEAP_SECONDS`].
+pub static DEFAULT_LEAP_SECOND_TABLE: LeapSecondTable<'static> =
+    LeapSecondTable::new(DEFAULT_LEAP_SECONDS);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_is_zero_before_first_entry() {
+        assert_eq!(DEFAULT_LEAP_SECOND_TABLE.utc_to_physical_offset(0), 0);
+    }
+
+    #[test]
+    fn offset_steps_up_across_a_threshold() {
+        let just_before = DEFAULT_LEAP_SECONDS[0].utc_threshold_secs - 1;
+        let at_threshold = DEFAULT_LEAP_SECONDS[0].utc_threshold_secs;
+        assert_eq!(DEFAULT_LEAP_SECOND_TABLE.utc_to_physical_offset(just_before), 0);
+        assert_eq!(
+            DEFAULT_LEAP_SECOND_TABLE.utc_to_physical_offset(at_threshold),
+            DEFAULT_LEAP_SECONDS[0].tai_minus_utc
+        );
+    }
+
+    #[test]
+    fn physical_offset_round_trips_utc_offset() {
+        for entry in DEFAULT_LEAP_SECONDS {
+            let physical = (entry.utc_threshold_secs as i64 + entry.tai_minus_utc) as u64;
+            assert_eq!(
+                DEFAULT_LEAP_SECOND_TABLE.physical_to_utc_offset(physical),
+                entry.tai_minus_utc
+            );
+        }
+    }
+
+    #[test]
+    fn leap_second_insertion_is_strictly_monotonic_in_physical_time() {
+        // The UTC seconds just before and at a leap-second threshold (i.e.
+        // 23:59:59 and the following 00:00:00, around the inserted 23:59:60)
+        // must map to distinct, increasing physical seconds rather than
+        // repeating.
+        let threshold = DEFAULT_LEAP_SECONDS[0].utc_threshold_secs;
+        let before_offset = DEFAULT_LEAP_SECOND_TABLE.utc_to_physical_offset(threshold - 1);
+        let after_offset = DEFAULT_LEAP_SECOND_TABLE.utc_to_physical_offset(threshold);
+        let physical_before = (threshold - 1) as i64 + before_offset;
+        let physical_after = threshold as i64 + after_offset;
+        assert!(physical_after > physical_before);
+    }
+}
@@ -0,0 +1,30 @@
+use super::ClockSource;
+use crate::Result;
+
+/// A clock source driven entirely by explicit calls to [`set_time`](Self::set_time),
+/// used for tests and for embedding this crate's HLC logic in systems that
+/// already have their own notion of physical time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ManualClock {
+    time: u64,
+}
+
+impl ManualClock {
+    /// Creates a new `ManualClock` starting at `time`.
+    pub fn new(time: u64) -> Self {
+        ManualClock { time }
+    }
+
+    /// Sets the current time returned by `now()`.
+    pub fn set_time(&mut self, time: u64) {
+        self.time = time;
+    }
+}
+
+impl ClockSource for ManualClock {
+    type Time = u64;
+    type Delta = u64;
+    fn now(&mut self) -> Result<Self::Time> {
+        Ok(self.time)
+    }
+}
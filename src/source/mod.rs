@@ -0,0 +1,41 @@
+//! Sources of physical time for a [`Clock`](crate::Clock) to read from.
+
+use crate::Result;
+
+/// The number of nanoseconds in a second.
+pub const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// A source of physical time that a [`Clock`](crate::Clock) can read and
+/// merge with observed remote timestamps to produce a hybrid logical clock.
+///
+/// Implementations need not be monotonic on their own; the `Clock` wrapper
+/// is responsible for folding a non-monotonic source into a monotonic
+/// `Timestamp`.
+pub trait ClockSource {
+    /// The representation of physical time returned by this source, e.g.
+    /// [`WallMST`] or a bare `u64` for tests.
+    type Time: Ord + Copy;
+    /// The representation of the difference between two `Time`s.
+    type Delta;
+
+    /// Returns the current physical time.
+    fn now(&mut self) -> Result<Self::Time>;
+}
+
+pub mod cuc;
+pub mod leap;
+mod manual;
+mod wall_ms;
+
+#[cfg(feature = "std")]
+mod monotonic;
+
+pub use self::leap::{LeapSecondEntry, LeapSecondTable};
+pub use self::manual::ManualClock;
+pub use self::wall_ms::WallMST;
+
+#[cfg(feature = "std")]
+pub use self::wall_ms::WallMS;
+
+#[cfg(feature = "std")]
+pub use self::monotonic::MonotonicWall;
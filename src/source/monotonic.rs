@@ -0,0 +1,63 @@
+use std::time::{Duration, Instant, SystemTime};
+
+use super::{ClockSource, WallMST};
+use crate::Result;
+
+/// A clock source that reports the same [`WallMST`] physical time as
+/// [`WallMS`](super::WallMS), but anchors it to a monotonic [`Instant`] so
+/// the physical component it returns can never move backward within a
+/// process, even when the system wall clock is stepped (NTP corrections,
+/// `settimeofday`, and the like).
+///
+/// It works by recording a `(SystemTime, Instant)` pair once, then computing
+/// `anchor_systemtime + (Instant::now() - anchor_instant)` for every
+/// subsequent reading. Because `Instant` is guaranteed monotonic by the
+/// standard library, the computed time is as well, at the cost of slowly
+/// drifting away from the true wall clock until the next re-anchor.
+#[derive(Debug, Clone, Copy)]
+pub struct MonotonicWall {
+    anchor_systemtime: SystemTime,
+    anchor_instant: Instant,
+}
+
+impl MonotonicWall {
+    /// Creates a new source, anchored to the current wall clock and instant.
+    pub fn new() -> Self {
+        MonotonicWall {
+            anchor_systemtime: SystemTime::now(),
+            anchor_instant: Instant::now(),
+        }
+    }
+
+    /// Returns the current computed wall-clock time without re-anchoring.
+    fn computed_systemtime(&self) -> SystemTime {
+        self.anchor_systemtime + self.anchor_instant.elapsed()
+    }
+
+    /// Re-syncs the anchor to the current `SystemTime`, but only if doing so
+    /// would move the computed time forward; a `SystemTime::now()` that has
+    /// fallen behind the currently-computed time is ignored, preserving the
+    /// non-decreasing invariant across re-syncs.
+    pub fn resync(&mut self) {
+        let computed = self.computed_systemtime();
+        let now = SystemTime::now();
+        if now > computed {
+            self.anchor_systemtime = now;
+            self.anchor_instant = Instant::now();
+        }
+    }
+}
+
+impl Default for MonotonicWall {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockSource for MonotonicWall {
+    type Time = WallMST;
+    type Delta = Duration;
+    fn now(&mut self) -> Result<Self::Time> {
+        WallMST::from_timespec(self.computed_systemtime())
+    }
+}
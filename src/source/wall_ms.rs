@@ -1,12 +1,19 @@
-use std::convert::TryInto;
-use std::fmt;
-use std::ops::Sub;
-use std::time::{Duration, SystemTime};
+use core::convert::TryInto;
+use core::fmt;
+use core::ops::Sub;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
 
-use super::{ClockSource, NANOS_PER_SEC};
+#[cfg(feature = "std")]
+use super::ClockSource;
+use super::NANOS_PER_SEC;
 use crate::{Result, Timestamp};
 
-// A clock source that returns wall-clock in 2^(-16)s
+// A clock source that returns wall-clock in 2^(-16)s. Requires `std` because
+// it reads `SystemTime::now()`; the `WallMST` representation it produces is
+// itself available without `std`.
+#[cfg(feature = "std")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct WallMS;
 /// Representation of our timestamp.
@@ -32,6 +39,63 @@ impl Timestamp<WallMST> {
             count,
         }
     }
+
+    /// Returns the 8-byte external TAI64 label for this timestamp's physical
+    /// component, discarding the HLC `count` and any sub-second fraction.
+    ///
+    /// A TAI64 label is `2^62 + s`, where `s` is the number of TAI seconds
+    /// since 1970-01-01 00:00:00 TAI, encoded big-endian so that labels sort
+    /// the same as chronological order.
+    pub fn to_tai64(&self) -> Result<[u8; 8]> {
+        let label = WallMST::unix_secs_to_tai64_label(self.time.duration_since_epoch()?.as_secs());
+        Ok(label.to_be_bytes())
+    }
+
+    /// Reconstructs a `Timestamp<WallMST>` from an 8-byte TAI64 label.
+    ///
+    /// The HLC `count` cannot be recovered from a bare TAI64 label, so it is
+    /// set to `0`; use [`to_tai64n`](Self::to_tai64n)/[`from_tai64n`](Self::from_tai64n)
+    /// when the count needs to round-trip.
+    pub fn from_tai64(bytes: [u8; 8]) -> Result<Self> {
+        let label = u64::from_be_bytes(bytes);
+        let secs = WallMST::tai64_label_to_unix_secs(label)?;
+        Ok(Timestamp {
+            time: WallMST::from_since_epoch(Duration::new(secs, 0))?,
+            count: 0,
+        })
+    }
+
+    /// Returns the 14-byte external-label encoding of this timestamp: a
+    /// 12-byte TAI64N label (the physical time, to nanosecond precision)
+    /// followed by the HLC `count` as a trailing 2-byte big-endian field,
+    /// mirroring how [`to_bytes`](Self::to_bytes) appends `count` after the
+    /// physical fields.
+    ///
+    /// TAI64N labels sort lexicographically the same as chronological order,
+    /// and appending `count` big-endian preserves that property for the full
+    /// 14-byte encoding.
+    pub fn to_tai64n(&self) -> Result<[u8; 14]> {
+        let since_epoch = self.time.duration_since_epoch()?;
+        let label = WallMST::unix_secs_to_tai64_label(since_epoch.as_secs());
+        let mut res = [0; 14];
+        res[0..8].copy_from_slice(&label.to_be_bytes());
+        res[8..12].copy_from_slice(&since_epoch.subsec_nanos().to_be_bytes());
+        res[12..14].copy_from_slice(&self.count.to_be_bytes());
+        Ok(res)
+    }
+
+    /// Reconstructs a `Timestamp<WallMST>` from a 14-byte TAI64N-plus-count
+    /// encoding produced by [`to_tai64n`](Self::to_tai64n).
+    pub fn from_tai64n(bytes: [u8; 14]) -> Result<Self> {
+        let label = u64::from_be_bytes(bytes[0..8].try_into().unwrap());
+        let nanos = u32::from_be_bytes(bytes[8..12].try_into().unwrap());
+        let count = u16::from_be_bytes(bytes[12..14].try_into().unwrap());
+        let secs = WallMST::tai64_label_to_unix_secs(label)?;
+        Ok(Timestamp {
+            time: WallMST::from_since_epoch(Duration::new(secs, nanos))?,
+            count,
+        })
+    }
 }
 
 impl WallMST {
@@ -51,24 +115,73 @@ impl WallMST {
     }
 
     /// Returns a `SystemTime` representing this timestamp.
+    #[cfg(feature = "std")]
     pub fn as_systemtime(self) -> Result<SystemTime> {
         Ok(SystemTime::UNIX_EPOCH + self.duration_since_epoch()?)
     }
 
     /// Returns a `WallMST` representing the `SystemTime`.
+    #[cfg(feature = "std")]
     pub fn from_timespec(t: SystemTime) -> Result<Self> {
         // TODO: use Duration::as_nanos
         let since_epoch = t.duration_since(SystemTime::UNIX_EPOCH)?;
         Self::from_since_epoch(since_epoch)
     }
 
+    /// Like [`from_timespec`](Self::from_timespec), but leap-second-aware;
+    /// see [`from_since_epoch_leap_aware`](Self::from_since_epoch_leap_aware).
+    #[cfg(feature = "std")]
+    pub fn from_timespec_leap_aware(t: SystemTime, table: &super::LeapSecondTable<'_>) -> Result<Self> {
+        let since_epoch = t.duration_since(SystemTime::UNIX_EPOCH)?;
+        Self::from_since_epoch_leap_aware(since_epoch, table)
+    }
+
     /// Returns a `WallMST` from a `Duration` since the unix epoch.
+    ///
+    /// Fails with [`Error::InvalidEncoding`](crate::Error::InvalidEncoding)
+    /// if `since_epoch` is so far in the future that it overflows the tick
+    /// count, which matters for [`from_tai64`](Timestamp::from_tai64) and
+    /// [`from_tai64n`](Timestamp::from_tai64n): those decode untrusted,
+    /// wire-supplied labels and must reject out-of-range input rather than
+    /// panic.
     pub fn from_since_epoch(since_epoch: Duration) -> Result<Self> {
         let nanos_per_tick = crate::source::NANOS_PER_SEC / WallMST::TICKS_PER_SEC;
-        let ticks = (since_epoch.as_secs() * WallMST::TICKS_PER_SEC) + (since_epoch.subsec_nanos() as u64/nanos_per_tick);
+        let secs_ticks = since_epoch
+            .as_secs()
+            .checked_mul(WallMST::TICKS_PER_SEC)
+            .ok_or(crate::Error::InvalidEncoding)?;
+        let ticks = secs_ticks
+            .checked_add(since_epoch.subsec_nanos() as u64 / nanos_per_tick)
+            .ok_or(crate::Error::InvalidEncoding)?;
         Ok(WallMST::of_u64(ticks))
     }
 
+    /// Like [`from_since_epoch`](Self::from_since_epoch), but adds the
+    /// `table`'s TAI-UTC offset for `since_epoch` so that the resulting
+    /// physical component is strictly monotonic across a positive UTC leap
+    /// second, rather than repeating or running backward.
+    ///
+    /// Use [`duration_since_epoch_leap_aware`](Self::duration_since_epoch_leap_aware)
+    /// with the same table to recover the original UTC `Duration`.
+    pub fn from_since_epoch_leap_aware(
+        since_epoch: Duration,
+        table: &super::LeapSecondTable<'_>,
+    ) -> Result<Self> {
+        let offset = table.utc_to_physical_offset(since_epoch.as_secs());
+        let physical_secs = (since_epoch.as_secs() as i64 + offset) as u64;
+        Self::from_since_epoch(Duration::new(physical_secs, since_epoch.subsec_nanos()))
+    }
+
+    /// Inverse of [`from_since_epoch_leap_aware`](Self::from_since_epoch_leap_aware):
+    /// returns the UTC `Duration` since the unix epoch that produced this
+    /// timestamp's leap-second-adjusted physical component.
+    pub fn duration_since_epoch_leap_aware(self, table: &super::LeapSecondTable<'_>) -> Result<Duration> {
+        let physical = self.duration_since_epoch()?;
+        let offset = table.physical_to_utc_offset(physical.as_secs());
+        let utc_secs = (physical.as_secs() as i64 - offset) as u64;
+        Ok(Duration::new(utc_secs, physical.subsec_nanos()))
+    }
+
     /// Returns the number of ticks since the unix epoch.
     fn as_u64(self) -> u64 {
         ((self.0 as u64 + Self::EPOCH_2020) * Self::TICKS_PER_SEC) + self.1 as u64
@@ -80,6 +193,34 @@ impl WallMST {
         let minor_ticks = (val % Self::TICKS_PER_SEC) as u16;
         WallMST(secs, minor_ticks)
     }
+
+    /// TAI seconds ahead of UTC at the time this was written (the 2016-12-31
+    /// leap second), used by `to_tai64`/`to_tai64n`. Prefer
+    /// [`from_since_epoch_leap_aware`](Self::from_since_epoch_leap_aware) and
+    /// a [`LeapSecondTable`](super::LeapSecondTable) when historical accuracy
+    /// across leap seconds matters.
+    const TAI_MINUS_UTC: i64 = 37;
+
+    /// Converts a count of UTC seconds since the unix epoch into a TAI64
+    /// label (`2^62 + tai_seconds`).
+    fn unix_secs_to_tai64_label(unix_secs: u64) -> u64 {
+        let tai_secs = unix_secs as i64 + Self::TAI_MINUS_UTC;
+        (1i64 << 62).wrapping_add(tai_secs) as u64
+    }
+
+    /// Inverse of [`unix_secs_to_tai64_label`](Self::unix_secs_to_tai64_label).
+    ///
+    /// `label` comes straight off the wire in `from_tai64`/`from_tai64n`, so
+    /// this rejects with [`Error::InvalidEncoding`](crate::Error::InvalidEncoding)
+    /// rather than panicking when `label` is so far from `2^62` that
+    /// subtracting [`TAI_MINUS_UTC`](Self::TAI_MINUS_UTC) would overflow `i64`.
+    fn tai64_label_to_unix_secs(label: u64) -> Result<u64> {
+        let tai_secs = (label as i64).wrapping_sub(1i64 << 62);
+        let unix_secs = tai_secs
+            .checked_sub(Self::TAI_MINUS_UTC)
+            .ok_or(crate::Error::InvalidEncoding)?;
+        Ok(unix_secs.max(0) as u64)
+    }
 }
 
 impl Sub for WallMST {
@@ -92,6 +233,29 @@ impl Sub for WallMST {
     }
 }
 
+impl WallMST {
+    /// Converts a `Duration` into a (possibly rounded-down) number of ticks.
+    fn duration_to_ticks(d: Duration) -> u64 {
+        let nanos_per_tick = NANOS_PER_SEC / Self::TICKS_PER_SEC;
+        d.as_secs() * Self::TICKS_PER_SEC + (d.subsec_nanos() as u64) / nanos_per_tick
+    }
+}
+
+impl core::ops::Add<Duration> for WallMST {
+    type Output = WallMST;
+    fn add(self, rhs: Duration) -> Self::Output {
+        WallMST::of_u64(self.as_u64() + Self::duration_to_ticks(rhs))
+    }
+}
+
+impl core::ops::Sub<Duration> for WallMST {
+    type Output = WallMST;
+    fn sub(self, rhs: Duration) -> Self::Output {
+        WallMST::of_u64(self.as_u64().saturating_sub(Self::duration_to_ticks(rhs)))
+    }
+}
+
+#[cfg(feature = "std")]
 impl ClockSource for WallMS {
     type Time = WallMST;
     type Delta = Duration;
@@ -101,7 +265,7 @@ impl ClockSource for WallMS {
 }
 
 impl fmt::Display for WallMST {
-    #[cfg(not(feature = "pretty-print"))]
+    #[cfg(not(all(feature = "pretty-print", feature = "std")))]
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.duration_since_epoch() {
             Ok(epoch) => write!(fmt, "{}", epoch.as_secs_f64()),
@@ -109,7 +273,7 @@ impl fmt::Display for WallMST {
         }
     }
 
-    #[cfg(feature = "pretty-print")]
+    #[cfg(all(feature = "pretty-print", feature = "std"))]
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.as_systemtime() {
             Ok(ts) => {
@@ -129,12 +293,20 @@ impl fmt::Display for WallMST {
 #[cfg(test)]
 mod tests {
     use super::WallMST;
-    use crate::tests::timestamps;
     use crate::Timestamp;
     use suppositions::generators::*;
 
     use suppositions::*;
 
+    fn timestamps<C: Generator + 'static>(
+        times: C,
+    ) -> Box<dyn GeneratorObject<Item = Timestamp<C::Item>>> {
+        let counts = u16s();
+        (times, counts)
+            .map(|(time, count)| Timestamp { time, count })
+            .boxed()
+    }
+
     fn wallclocks2() -> Box<dyn GeneratorObject<Item = WallMST>> {
         u64s()
             .map(|val| {
@@ -197,4 +369,52 @@ mod tests {
             ta.cmp(&tb) == ba.cmp(&bb)
         })
     }
+
+    #[test]
+    fn should_round_trip_via_tai64n() {
+        property(timestamps(wallclocks2())).check(|ts| {
+            let bs = ts.to_tai64n().expect("tai64n");
+            let ts2 = Timestamp::<WallMST>::from_tai64n(bs).expect("from tai64n");
+            ts == ts2
+        });
+    }
+
+    #[test]
+    fn tai64n_repr_should_order_as_timestamps() {
+        property((timestamps(wallclocks2()), timestamps(wallclocks2()))).check(|(ta, tb)| {
+            use std::cmp::Ord;
+
+            let ba = ta.to_tai64n().expect("tai64n");
+            let bb = tb.to_tai64n().expect("tai64n");
+            ta.cmp(&tb) == ba.cmp(&bb)
+        })
+    }
+
+    #[test]
+    fn from_tai64_rejects_labels_that_overflow_instead_of_panicking() {
+        // A label far enough from `2^62` that recovering unix seconds
+        // underflows `i64`; this used to panic (or, in release, silently
+        // wrap to a bogus time) instead of returning an error.
+        let label = [0xC0, 0, 0, 0, 0, 0, 0, 0];
+        assert!(Timestamp::<WallMST>::from_tai64(label).is_err());
+    }
+
+    #[test]
+    fn leap_aware_round_trip_preserves_utc_duration() {
+        use crate::source::leap::DEFAULT_LEAP_SECOND_TABLE;
+        use std::time::Duration;
+
+        // `WallMST` only represents seconds from `EPOCH_2020` onward, so keep
+        // the generated UTC seconds within that representable range (as
+        // `wallclocks2` above does for the plain, non-leap-aware case).
+        property(u32s()).check(|offset| {
+            let since_epoch = Duration::new(WallMST::EPOCH_2020 + offset as u64, 0);
+            let wall = WallMST::from_since_epoch_leap_aware(since_epoch, &DEFAULT_LEAP_SECOND_TABLE)
+                .expect("leap aware");
+            let round_tripped = wall
+                .duration_since_epoch_leap_aware(&DEFAULT_LEAP_SECOND_TABLE)
+                .expect("leap aware reverse");
+            round_tripped == since_epoch
+        });
+    }
 }
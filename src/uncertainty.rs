@@ -0,0 +1,209 @@
+//! Uncertainty-bounded physical time, borrowed from the error-interval idea
+//! in Byzantine-tolerant time synchronization (e.g. TrueTime): instead of
+//! trusting a single `now()` reading, a reading is a window of possible
+//! instants, and remote observations are accepted only when their windows
+//! are consistent with our own.
+
+use core::ops::{Add, Sub};
+
+use crate::error::Error;
+use crate::Result;
+
+/// The most peers [`ErrorInterval::merge_quorum`] will merge at once,
+/// chosen so it can sort indices on the stack without `alloc`.
+const MAX_QUORUM_PEERS: usize = 32;
+
+/// A window of possible physical times, bounded by [`earliest`](Self::earliest)
+/// and [`latest`](Self::latest).
+///
+/// Stored as the two bounds directly, rather than as a `midpoint`/`error`
+/// pair: [`intersect`](Self::intersect) narrows the window to an arbitrary,
+/// generally asymmetric, sub-range that a symmetric `midpoint ± error`
+/// representation couldn't reproduce without a division `T`/`D` aren't
+/// guaranteed to support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorInterval<T, D> {
+    earliest: T,
+    latest: T,
+    _error: core::marker::PhantomData<D>,
+}
+
+impl<T, D> ErrorInterval<T, D>
+where
+    T: Copy + Ord + Add<D, Output = T> + Sub<D, Output = T>,
+    D: Copy + Ord,
+{
+    /// Creates an interval centered on `midpoint`, `error` wide in either
+    /// direction.
+    pub fn new(midpoint: T, error: D) -> Self {
+        ErrorInterval {
+            earliest: midpoint - error,
+            latest: midpoint + error,
+            _error: core::marker::PhantomData,
+        }
+    }
+
+    /// The earliest instant this interval considers possible.
+    pub fn earliest(&self) -> T {
+        self.earliest
+    }
+
+    /// The latest instant this interval considers possible.
+    pub fn latest(&self) -> T {
+        self.latest
+    }
+
+    /// Whether this interval and `other` describe overlapping windows of
+    /// possible physical time.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.earliest <= other.latest && other.earliest <= self.latest
+    }
+}
+
+impl<T, D> ErrorInterval<T, D>
+where
+    T: Copy + Ord + Sub<Output = D>,
+    D: Copy + Ord,
+{
+    /// The current error estimate: how wide a window of possible physical
+    /// times this interval still represents (`latest() - earliest()`).
+    pub fn error(&self) -> D {
+        self.latest - self.earliest
+    }
+}
+
+impl<T, D> ErrorInterval<T, D>
+where
+    T: Copy + Ord + Add<D, Output = T> + Sub<D, Output = T>,
+    D: Copy + Ord,
+{
+    /// Intersects this interval with `other`, returning the (necessarily
+    /// narrower, or equal) overlap, or `None` if they don't overlap at all.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let lo = self.earliest.max(other.earliest);
+        let hi = self.latest.min(other.latest);
+        if lo > hi {
+            return None;
+        }
+        Some(ErrorInterval {
+            earliest: lo,
+            latest: hi,
+            _error: core::marker::PhantomData,
+        })
+    }
+
+    /// Merges several peer observations into one conservative interval by
+    /// intersecting them, after discarding the `outliers_to_discard` lowest
+    /// and highest intervals by `earliest()` so that a minority of
+    /// misbehaving or badly-lagging peers can't unduly narrow, or
+    /// spuriously invalidate, the result.
+    ///
+    /// Returns `None` if fewer than `2 * outliers_to_discard + 1` intervals
+    /// are given (no quorum survives discarding outliers), or if the
+    /// surviving intervals don't all overlap.
+    pub fn merge_quorum(intervals: &[Self], outliers_to_discard: usize) -> Option<Self> {
+        if intervals.len() > MAX_QUORUM_PEERS || intervals.len() <= 2 * outliers_to_discard {
+            return None;
+        }
+
+        let mut order = [0usize; MAX_QUORUM_PEERS];
+        for (i, slot) in order.iter_mut().enumerate().take(intervals.len()) {
+            *slot = i;
+        }
+        let order = &mut order[..intervals.len()];
+        order.sort_unstable_by_key(|&i| intervals[i].earliest());
+        let kept = &order[outliers_to_discard..intervals.len() - outliers_to_discard];
+
+        let mut merged = intervals[kept[0]];
+        for &i in &kept[1..] {
+            merged = merged.intersect(&intervals[i])?;
+        }
+        Some(merged)
+    }
+}
+
+/// An [`ErrorInterval`]-aware companion to [`ClockSource`](crate::ClockSource):
+/// a source that can additionally report how uncertain its reading is.
+pub trait UncertainClockSource: crate::ClockSource {
+    /// Returns the current physical time as an [`ErrorInterval`] rather
+    /// than a single point.
+    fn now_with_error(&mut self) -> Result<ErrorInterval<Self::Time, Self::Delta>>;
+}
+
+/// Checks whether `remote` may be accepted given our own current `local`
+/// interval and a configured `max_diff` bound, matching
+/// [`Clock::with_max_diff`](crate::Clock::with_max_diff)'s semantics:
+/// rejected only when `remote`'s earliest bound is more than `max_diff`
+/// past `local`'s latest bound.
+pub fn accept_remote<T, D>(
+    local: &ErrorInterval<T, D>,
+    remote: &ErrorInterval<T, D>,
+    max_diff: D,
+) -> Result<()>
+where
+    T: Copy + Ord + Add<D, Output = T> + Sub<D, Output = T> + Sub<Output = D>,
+    D: Copy + Ord,
+{
+    if remote.overlaps(local) {
+        return Ok(());
+    }
+    // A remote wholly earlier than `local` is accepted; only compute the
+    // difference once it is known to be non-negative.
+    if remote.earliest() > local.latest() && remote.earliest() - local.latest() > max_diff {
+        return Err(Error::OffsetTooGreat);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iv(midpoint: i64, error: i64) -> ErrorInterval<i64, i64> {
+        ErrorInterval::new(midpoint, error)
+    }
+
+    #[test]
+    fn overlapping_intervals_intersect() {
+        let a = iv(10, 5); // [5, 15]
+        let b = iv(12, 5); // [7, 17]
+        let merged = a.intersect(&b).expect("overlap");
+        assert_eq!(merged.earliest(), 7);
+        assert_eq!(merged.latest(), 15);
+    }
+
+    #[test]
+    fn disjoint_intervals_do_not_intersect() {
+        let a = iv(0, 1); // [-1, 1]
+        let b = iv(10, 1); // [9, 11]
+        assert!(a.intersect(&b).is_none());
+    }
+
+    #[test]
+    fn accept_remote_within_bound() {
+        let local = iv(0, 0);
+        let remote = iv(5, 0);
+        assert!(accept_remote(&local, &remote, 10).is_ok());
+    }
+
+    #[test]
+    fn accept_remote_rejects_beyond_bound() {
+        let local = iv(0, 0);
+        let remote = iv(20, 0);
+        assert!(accept_remote(&local, &remote, 10).is_err());
+    }
+
+    #[test]
+    fn merge_quorum_discards_outliers() {
+        let intervals = [iv(10, 1), iv(11, 1), iv(9, 1), iv(1000, 1), iv(-1000, 1)];
+        let merged = ErrorInterval::merge_quorum(&intervals, 1).expect("quorum");
+        assert!(merged.earliest() >= 8);
+        assert!(merged.latest() <= 12);
+    }
+
+    #[test]
+    fn merge_quorum_needs_enough_peers() {
+        let intervals = [iv(0, 1), iv(1, 1)];
+        assert!(ErrorInterval::merge_quorum(&intervals, 1).is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use utsl_hybrid_clocks::{Clock, ManualClock, Result, Timestamp};
+use utsl_hybrid_clocks::{Clock, ErrorInterval, ManualClock, Result, Timestamp};
 use suppositions::generators::*;
 use suppositions::*;
 
@@ -291,3 +291,49 @@ fn should_observe_past_timestamp() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn should_ignore_intervals_too_far_forward() -> Result<()> {
+    let src = ManualClock::new(0);
+    let mut clock = Clock::new(src)?.with_max_diff(10);
+    assert!(clock.observe_interval(0, &ErrorInterval::new(11, 0)).is_err());
+
+    clock.observe_interval(0, &ErrorInterval::new(1, 0)).unwrap();
+    assert_eq!(
+        clock.now().expect("now"),
+        Timestamp {
+            time: 1,
+            count: 0
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn should_account_for_time_passing_when_checking_interval_max_error() -> Result<()> {
+    let src = ManualClock::new(0);
+    let mut clock = Clock::new(src)?.with_max_diff(10);
+    clock.inner_mut().set_time(1);
+
+    assert!(clock.observe_interval(0, &ErrorInterval::new(11, 0)).is_ok());
+    Ok(())
+}
+
+// `observe_interval` folded `remote` into `accept_remote`'s check but never
+// merged it into `self.last`, so a later `now()` could be causally ordered
+// before what was just observed.
+#[test]
+fn observe_interval_advances_last_so_now_stays_causally_after() -> Result<()> {
+    let src = ManualClock::new(0);
+    let mut clock = Clock::new(src)?;
+
+    clock.observe_interval(0, &ErrorInterval::new(10, 0))?;
+    assert_eq!(
+        clock.now()?,
+        Timestamp {
+            time: 10,
+            count: 0
+        }
+    );
+    Ok(())
+}
+